@@ -2,40 +2,56 @@ use super::util;
 use super::{EventLoop, LoopHandle};
 use bevy::app::PluginsState;
 use bevy::ecs::system::SystemState;
+use bevy::input::mouse::{MouseButtonInput, MouseWheel};
+use bevy::input::touch::{TouchInput, TouchPhase as BevyTouchPhase};
+use bevy::input::ButtonState as BevyButtonState;
 use bevy::prelude::*;
 use bevy::render::camera::{ManualTextureViewHandle, ManualTextureViews, RenderTarget};
 use bevy::render::extract_resource::ExtractResource;
 use bevy::render::renderer::{RenderAdapter, RenderDevice};
 use bevy::render::texture::GpuImage;
 use bevy::utils::HashMap;
-use bevy::window::{PrimaryWindow, WindowResolution};
+use bevy::window::{CursorMoved, PrimaryWindow, WindowResolution};
 use smithay::backend::allocator::gbm::{GbmAllocator, GbmBufferFlags, GbmDevice};
 use smithay::backend::allocator::{self, Fourcc, Modifier};
 use smithay::backend::drm::gbm::Error as GbmError;
-use smithay::backend::drm::{
-    DrmDevice, DrmDeviceFd, DrmError, DrmEvent, DrmNode, GbmBufferedSurface, PlaneClaim,
+use smithay::backend::drm::{DrmDevice, DrmDeviceFd, DrmError, DrmEvent, DrmNode, GbmBufferedSurface};
+use smithay::backend::input::{
+    AbsolutePositionEvent as _, Axis, AxisSource, ButtonState, Event, InputEvent, KeyState,
+    KeyboardKeyEvent as _, PointerAxisEvent as _, PointerButtonEvent as _, PointerMotionEvent as _,
+    TouchEvent as _,
+};
+use smithay::backend::libinput::{
+    LibinputInputBackend, LibinputPointerAxisEvent, LibinputSessionInterface,
 };
-use smithay::backend::input::{Event, InputEvent, KeyboardKeyEvent as _};
-use smithay::backend::libinput::{LibinputInputBackend, LibinputSessionInterface};
 use smithay::backend::session::libseat::LibSeatSession;
-use smithay::backend::session::Session;
+use smithay::backend::session::{Event as SessionEvent, Session};
 use smithay::backend::udev::{UdevBackend, UdevEvent};
 use smithay::desktop::{PopupManager, Space, Window, WindowSurfaceType};
 use smithay::input::keyboard::{FilterResult, KeysymHandle, ModifiersState, XkbConfig};
+use smithay::input::pointer::{AxisFrame, ButtonEvent, MotionEvent};
 use smithay::input::{Seat, SeatState};
 use smithay::output::{Mode, Output, PhysicalProperties, Subpixel};
 use smithay::reexports::calloop::generic::Generic;
 use smithay::reexports::calloop::{InsertError, Interest, PostAction};
+use smithay::reexports::drm::control::{connector, crtc, Mode as DrmMode};
 use smithay::reexports::gbm;
 use smithay::reexports::input::event::keyboard::KeyboardKeyEvent;
+use smithay::reexports::input::event::pointer::{
+    PointerButtonEvent, PointerMotionAbsoluteEvent, PointerMotionEvent,
+};
+use smithay::reexports::input::event::touch::{
+    TouchCancelEvent, TouchDownEvent, TouchFrameEvent, TouchMotionEvent, TouchUpEvent,
+};
 use smithay::reexports::input::Libinput;
 use smithay::reexports::rustix::fs::OFlags;
 use smithay::reexports::wayland_server::backend::{
     ClientData, ClientId, DisconnectReason, InitError,
 };
 use smithay::reexports::wayland_server::protocol::wl_shm::Format;
+use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
 use smithay::reexports::wayland_server::{BindError, Display, DisplayHandle};
-use smithay::utils::{DeviceFd, Size, Transform, SERIAL_COUNTER};
+use smithay::utils::{DeviceFd, Logical, Point, Size, Transform, SERIAL_COUNTER};
 use smithay::wayland::compositor::{CompositorClientState, CompositorState};
 use smithay::wayland::dmabuf::{DmabufFeedbackBuilder, DmabufGlobal, DmabufState};
 use smithay::wayland::keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitState;
@@ -54,11 +70,14 @@ use std::{io, iter};
 
 mod buffer;
 mod compositor;
+mod damage;
 mod data_control;
 mod data_device;
+mod device;
 mod dmabuf;
 mod input_method;
 mod keyboard_shortcuts_inhibit;
+mod layout;
 mod output;
 mod primary_selection;
 mod seat;
@@ -72,6 +91,9 @@ mod xdg_shell;
 
 const SUPPORTED_FORMATS: &[Format] = &[Format::Argb8888, Format::Xrgb8888];
 
+/// Logical pixels a grow/shrink-column keybind adjusts a column's width by.
+const COLUMN_RESIZE_STEP: i32 = 80;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("wayland display: {0}")]
@@ -106,6 +128,9 @@ pub enum Error {
 
     #[error("Invalid GBM descriptor: {0}")]
     InvalidGbmDescriptor(#[from] gbm::InvalidFdError),
+
+    #[error("no device path for {0:?}")]
+    NoDevPath(DrmNode),
 }
 
 impl<T> From<InsertError<T>> for Error {
@@ -114,6 +139,30 @@ impl<T> From<InsertError<T>> for Error {
     }
 }
 
+/// Connected connectors known to `scanner`, paired with their CRTC and
+/// preferred mode, skipping connectors with no CRTC or no mode.
+fn connected_connectors(
+    scanner: &DrmScanner<SimpleCrtcMapper>,
+) -> Vec<(connector::Handle, crtc::Handle, DrmMode)> {
+    scanner
+        .connectors()
+        .iter()
+        .filter(|(_, info)| info.state() == connector::State::Connected)
+        .filter_map(|(handle, info)| {
+            let crtc = scanner.crtc_for_connector(handle)?;
+            let mode = *info.modes().iter().next()?;
+
+            Some((*handle, crtc, mode))
+        })
+        .collect()
+}
+
+/// Maps a libinput touch slot to the stable `u64` id Bevy's `TouchInput`
+/// expects, collapsing slot-less devices onto id `0`.
+fn touch_slot_id(slot: smithay::backend::input::TouchSlot) -> u64 {
+    slot.raw().map(u64::from).unwrap_or(0)
+}
+
 #[derive(Component)]
 pub struct DiagnosticText;
 
@@ -138,6 +187,7 @@ pub struct SmithayState {
     pub dmabuf_global: DmabufGlobal,
     pub dmabuf_state: DmabufState,
     pub keyboard_shortcuts_inhibit_state: KeyboardShortcutsInhibitState,
+    pub layout: layout::Layout,
     pub output: Output,
     pub output_manager_state: OutputManagerState,
     pub primary_selection_state: PrimarySelectionState,
@@ -146,6 +196,14 @@ pub struct SmithayState {
     pub seat_state: SeatState<SmithayAppRunnerState>,
     pub shm_state: ShmState,
     pub space: Space<Window>,
+    /// `true` until the first connector is added, at which point `output`
+    /// (until then an unmapped, global-less placeholder) is claimed and
+    /// updated in place to describe that connector, instead of leaving it
+    /// around as a second, stale `wl_output`.
+    output_is_placeholder: bool,
+    /// Logical X position the next connector is mapped at, so outputs land
+    /// side by side instead of stacked on top of each other.
+    next_output_x: i32,
     pub xdg_foreign_state: XdgForeignState,
     pub xdg_shell_state: XdgShellState,
     pub start_time: Instant,
@@ -173,6 +231,7 @@ impl SmithayState {
 
         let _pointer = seat.add_pointer();
         let _keyboard = seat.add_keyboard(XkbConfig::default(), 250, 45);
+        let _touch = seat.add_touch();
 
         let compositor_state = CompositorState::new::<SmithayAppRunnerState>(display_handle);
         let data_control_state =
@@ -214,8 +273,12 @@ impl SmithayState {
             refresh: 60_000,
         };
 
+        // Not mapped into `space` and has no `wl_output` global yet: it's
+        // just a placeholder so `output` has a sane value before the first
+        // connector is added. `add_connector` claims and updates this same
+        // `Output` in place for the first connector it sees.
         let output = Output::new(
-            "winit".to_string(),
+            "placeholder".to_string(),
             PhysicalProperties {
                 size: (2560, 1440).into(),
                 subpixel: Subpixel::Unknown,
@@ -224,8 +287,6 @@ impl SmithayState {
             },
         );
 
-        let _global = output.create_global::<SmithayAppRunnerState>(display_handle);
-
         output.change_current_state(
             Some(mode),
             Some(Transform::Flipped180),
@@ -235,8 +296,6 @@ impl SmithayState {
 
         output.set_preferred(mode);
 
-        space.map_output(&output, (0, 0));
-
         let start_time = Instant::now();
 
         Self {
@@ -246,6 +305,7 @@ impl SmithayState {
             dmabuf_global,
             dmabuf_state,
             keyboard_shortcuts_inhibit_state,
+            layout: layout::Layout::new(),
             output,
             output_manager_state,
             popup_manager,
@@ -254,6 +314,8 @@ impl SmithayState {
             seat_state,
             space,
             shm_state,
+            output_is_placeholder: true,
+            next_output_x: 0,
             xdg_foreign_state,
             xdg_shell_state,
             start_time,
@@ -264,13 +326,26 @@ impl SmithayState {
 pub struct SmithayAppRunnerState {
     pub app: App,
     pub display_handle: DisplayHandle,
+    pub loop_handle: LoopHandle<Self>,
     pub smithay_state: SmithayState,
-    pub drm_device: DrmDevice,
-    pub drm_node: DrmNode,
-    pub drm_scanner: DrmScanner<SimpleCrtcMapper>,
-    pub drm_plane_claim: PlaneClaim,
-    pub gbm_device: GbmDevice<DrmDeviceFd>,
-    pub gbm_surface: GbmBufferedSurface<GbmAllocator<DrmDeviceFd>, ()>,
+    pub devices: HashMap<DrmNode, device::Device>,
+    pub libinput: Libinput,
+    pub session: LibSeatSession,
+    pub session_paused: bool,
+    pub cursor_position: Point<f64, Logical>,
+    pub primary_window: Entity,
+    /// Set whenever input arrives; consumed by `run()` to decide whether
+    /// this tick's Bevy frame actually has anything new to scan out.
+    pub pending_render: bool,
+    /// Next [`ManualTextureViewHandle`] to hand a newly connected surface,
+    /// so every connected output keeps its own texture instead of all of
+    /// them fighting over handle `0`.
+    next_texture_handle: u32,
+    /// Last known position of each active touch point, keyed by
+    /// [`touch_slot_id`]. `cursor_position` only tracks the pointer, so
+    /// `Ended`/`Canceled` events need their own record of where that finger
+    /// actually was.
+    touch_positions: HashMap<u64, Point<f64, Logical>>,
 }
 
 impl SmithayAppRunnerState {
@@ -291,8 +366,7 @@ impl SmithayAppRunnerState {
         let source = ListeningSocketSource::new_auto()?;
 
         loop_handle.insert_source(session_notifier, |event, _metadata, state| {
-            dbg!(event);
-            // todo
+            state.on_session_event(event)
         })?;
 
         loop_handle.insert_source(udev, |event, _metadata, state| {
@@ -347,39 +421,114 @@ impl SmithayAppRunnerState {
         let drm_node = DrmNode::from_dev_id(primary_node).unwrap();
         //util::find_best_gpu(&seat_name).unwrap();
 
-        let drm_device_fd = dbg!(session.open(&dbg!(drm_node.dev_path().unwrap()), OFlags::RDWR))
+        let primary_window = {
+            use bevy::window::Window;
+
+            let mut system_state =
+                SystemState::<Query<(Entity, &mut Window), With<PrimaryWindow>>>::new(
+                    app.world_mut(),
+                );
+
+            let mut query = system_state.get_mut(app.world_mut());
+            let (primary_window, mut window) = query.single_mut();
+
+            window.resolution = WindowResolution::new(2560.0, 1440.0);
+
+            primary_window
+        };
+
+        let smithay_state = SmithayState::new(&display_handle, drm_node, &seat_name);
+
+        let mut state = Self {
+            app,
+            display_handle,
+            loop_handle,
+            smithay_state,
+            devices: HashMap::default(),
+            cursor_position: Point::from((1280.0, 720.0)),
+            primary_window,
+            pending_render: true,
+            next_texture_handle: 0,
+            touch_positions: HashMap::default(),
+            libinput: context,
+            session,
+            session_paused: false,
+        };
+
+        state.add_device(drm_node)?;
+
+        Ok(state)
+    }
+
+    /// Opens a newly discovered GPU, registers its DRM event source, and
+    /// creates a scanout [`device::Surface`] for each connected connector.
+    fn add_device(&mut self, node: DrmNode) -> Result<(), Error> {
+        let path = node.dev_path().ok_or(Error::NoDevPath(node))?;
+        let drm_device_fd = self
+            .session
+            .open(&path, OFlags::RDWR)
             .map(DeviceFd::from)
-            .map(DrmDeviceFd::new)
-            .unwrap();
+            .map(DrmDeviceFd::new)?;
 
-        let (mut drm_device, drm_device_notifier) = DrmDevice::new(drm_device_fd.clone(), true)?;
+        let (drm_device, drm_device_notifier) = DrmDevice::new(drm_device_fd.clone(), true)?;
 
-        loop_handle.insert_source(drm_device_notifier, |event, _metadata, state| {
-            state.on_drm_event(event)
-        })?;
+        let notifier_token = self
+            .loop_handle
+            .insert_source(drm_device_notifier, move |event, _metadata, state| {
+                state.on_drm_event(node, event)
+            })?;
 
+        let gbm_device = GbmDevice::new(drm_device_fd).map_err(Error::Gbm)?;
         let mut drm_scanner = DrmScanner::<SimpleCrtcMapper>::new();
-        let _result = drm_scanner
+        drm_scanner
             .scan_connectors(&drm_device)
             .map_err(Error::DrmScan)?;
 
-        let (connector, mode) = drm_scanner
-            .connectors()
-            .iter()
-            .find_map(|(connector, info)| {
-                let mode = *info.modes().iter().next()?;
+        let connected = connected_connectors(&drm_scanner);
 
-                Some((*connector, mode))
-            })
-            .unwrap();
+        let mut device = device::Device {
+            drm_device,
+            gbm_device,
+            drm_scanner,
+            surfaces: std::collections::HashMap::new(),
+            notifier_token,
+        };
 
-        let crtc = drm_scanner.crtc_for_connector(&connector).unwrap();
-        let gbm_device = GbmDevice::new(drm_device_fd).map_err(Error::Gbm)?;
-        let gbm_allocator = GbmAllocator::new(gbm_device.clone(), GbmBufferFlags::SCANOUT);
-        let plane = drm_device.planes(&crtc).unwrap().primary[0].handle;
-        let drm_plane_claim = drm_device.claim_plane(plane, crtc).unwrap();
-        let drm_surface = drm_device.create_surface(crtc, mode, &[connector]).unwrap();
-        let gbm_surface = GbmBufferedSurface::new(
+        for (connector, crtc, mode) in connected {
+            self.add_connector(node, &mut device, connector, crtc, mode);
+        }
+
+        self.devices.insert(node, device);
+
+        Ok(())
+    }
+
+    /// Claims a plane for `crtc` and builds a [`device::Surface`] scanning
+    /// `connector` out at `mode`, mapping a matching smithay [`Output`] into
+    /// `space`.
+    fn add_connector(
+        &mut self,
+        node: DrmNode,
+        device: &mut device::Device,
+        connector: connector::Handle,
+        crtc: crtc::Handle,
+        mode: DrmMode,
+    ) {
+        let Ok(planes) = device.drm_device.planes(&crtc) else {
+            return;
+        };
+
+        let Ok(plane_claim) = device.drm_device.claim_plane(planes.primary[0].handle, crtc) else {
+            return;
+        };
+
+        let Ok(drm_surface) = device.drm_device.create_surface(crtc, mode, &[connector]) else {
+            return;
+        };
+
+        let gbm_allocator = GbmAllocator::new(device.gbm_device.clone(), GbmBufferFlags::SCANOUT);
+
+        let Ok(gbm_surface) = GbmBufferedSurface::new(
             drm_surface,
             gbm_allocator,
             &[Fourcc::Abgr8888, Fourcc::Xrgb8888],
@@ -387,64 +536,462 @@ impl SmithayAppRunnerState {
                 code: Fourcc::Abgr8888,
                 modifier: Modifier::Linear,
             }),
-        )
-        .unwrap();
+        ) else {
+            return;
+        };
 
-        {
-            use bevy::window::Window;
+        let (width, height) = mode.size();
+        let output_mode = Mode {
+            size: Size::from((width as i32, height as i32)),
+            refresh: (mode.vrefresh() * 1000) as i32,
+        };
 
-            let mut system_state =
-                SystemState::<Query<&mut Window, With<PrimaryWindow>>>::new(app.world_mut());
+        // The first connector claims the placeholder `Output` created in
+        // `SmithayState::new` instead of leaving it unmapped and global-less
+        // forever while a second `Output` speaks for the real display.
+        let output = if self.smithay_state.output_is_placeholder {
+            self.smithay_state.output_is_placeholder = false;
+            self.smithay_state.output.clone()
+        } else {
+            Output::new(
+                format!("{node}-{connector:?}"),
+                PhysicalProperties {
+                    size: (0, 0).into(),
+                    subpixel: Subpixel::Unknown,
+                    make: "comp".into(),
+                    model: "comp".into(),
+                },
+            )
+        };
 
-            let mut query = system_state.get_mut(app.world_mut());
-            let mut primary_window = query.single_mut();
+        // Position this output to the right of every output already mapped,
+        // instead of stacking every connector on top of the same (0, 0).
+        let position: Point<i32, Logical> = (self.smithay_state.next_output_x, 0).into();
+        self.smithay_state.next_output_x += output_mode.size.w;
 
-            primary_window.resolution = WindowResolution::new(2560.0, 1440.0);
-        }
+        output.change_current_state(
+            Some(output_mode),
+            Some(Transform::Flipped180),
+            None,
+            Some(position),
+        );
 
-        let smithay_state = SmithayState::new(&display_handle, drm_node, &seat_name);
+        output.set_preferred(output_mode);
 
-        Ok(Self {
-            app,
-            display_handle,
-            smithay_state,
-            drm_node,
-            drm_device,
-            drm_scanner,
-            drm_plane_claim,
-            gbm_device,
-            gbm_surface,
-        })
+        let global = output.create_global::<Self>(&self.display_handle);
+        let texture_handle = ManualTextureViewHandle(self.next_texture_handle);
+        self.next_texture_handle += 1;
+
+        self.smithay_state.space.map_output(&output, position);
+
+        device.surfaces.insert(
+            crtc,
+            device::Surface {
+                connector,
+                output,
+                global,
+                texture_handle,
+                damage_tracker: damage::DamageTracker::new(),
+                plane_claim,
+                gbm_surface,
+            },
+        );
+    }
+
+    fn on_session_event(&mut self, event: SessionEvent) {
+        match event {
+            SessionEvent::PauseSession => {
+                self.session_paused = true;
+                self.libinput.suspend();
+
+                for device in self.devices.values_mut() {
+                    device.drm_device.pause();
+                }
+            }
+            SessionEvent::ActivateSession => {
+                if self.libinput.resume().is_err() {
+                    return;
+                }
+
+                for device in self.devices.values_mut() {
+                    if device.drm_device.activate(true).is_err() {
+                        continue;
+                    }
+
+                    let _ = device.drm_scanner.scan_connectors(&device.drm_device);
+
+                    // Force a full modeset and requeue so scanout resumes cleanly.
+                    for surface in device.surfaces.values_mut() {
+                        surface.gbm_surface.reset_buffers();
+                        surface.damage_tracker.force_full_damage();
+                    }
+                }
+
+                self.session_paused = false;
+            }
+        }
     }
 
     fn on_udev_event(&mut self, event: UdevEvent) {
         match event {
-            UdevEvent::Added { device_id, path } => {
-                dbg!(DrmNode::from_dev_id(device_id));
+            UdevEvent::Added { device_id, .. } => {
+                let Ok(node) = DrmNode::from_dev_id(device_id) else {
+                    return;
+                };
+
+                if let Err(error) = self.add_device(node) {
+                    eprintln!("failed to add drm device {node}: {error}");
+                }
             }
             UdevEvent::Changed { device_id } => {
-                dbg!(DrmNode::from_dev_id(device_id));
+                let Ok(node) = DrmNode::from_dev_id(device_id) else {
+                    return;
+                };
+
+                self.refresh_connectors(node);
             }
             UdevEvent::Removed { device_id } => {
-                dbg!(DrmNode::from_dev_id(device_id));
+                let Ok(node) = DrmNode::from_dev_id(device_id) else {
+                    return;
+                };
+
+                if let Some(device) = self.devices.remove(&node) {
+                    for surface in device.surfaces.into_values() {
+                        self.smithay_state.space.unmap_output(&surface.output);
+                        self.display_handle.remove_global::<Self>(surface.global);
+                    }
+
+                    self.loop_handle.remove(device.notifier_token);
+                }
             }
         }
     }
 
-    fn on_drm_event(&mut self, event: DrmEvent) {
-        match event {
-            DrmEvent::VBlank(handle) => {
-                self.gbm_surface.frame_submitted().unwrap();
+    /// Re-scans `node`'s connectors, creating surfaces for newly connected
+    /// outputs and tearing down surfaces for disconnected ones.
+    fn refresh_connectors(&mut self, node: DrmNode) {
+        let Some(mut device) = self.devices.remove(&node) else {
+            return;
+        };
+
+        if device.drm_scanner.scan_connectors(&device.drm_device).is_err() {
+            self.devices.insert(node, device);
+
+            return;
+        }
+
+        let connected = connected_connectors(&device.drm_scanner);
+        let live: std::collections::HashSet<_> =
+            connected.iter().map(|(_, crtc, _)| *crtc).collect();
+
+        let space = &mut self.smithay_state.space;
+        let display_handle = &self.display_handle;
+
+        device.surfaces.retain(|crtc, surface| {
+            if live.contains(crtc) {
+                true
+            } else {
+                space.unmap_output(&surface.output);
+                display_handle.remove_global::<Self>(surface.global.clone());
+
+                false
+            }
+        });
+
+        for (connector, crtc, mode) in connected {
+            if device.surfaces.contains_key(&crtc) {
+                continue;
             }
-            DrmEvent::Error(error) => {
-                //
+
+            self.add_connector(node, &mut device, connector, crtc, mode);
+        }
+
+        self.devices.insert(node, device);
+    }
+
+    fn on_drm_event(&mut self, node: DrmNode, event: DrmEvent) {
+        let Some(device) = self.devices.get_mut(&node) else {
+            return;
+        };
+
+        match event {
+            DrmEvent::VBlank(crtc) => {
+                if let Some(surface) = device.surfaces.get_mut(&crtc) {
+                    let _ = surface.gbm_surface.frame_submitted();
+                }
             }
+            DrmEvent::Error(_error) => {}
         }
     }
 
     fn on_input_event(&mut self, event: InputEvent<LibinputInputBackend>) {
-        if let InputEvent::Keyboard { event } = event {
-            self.on_keyboard_event(event)
+        self.pending_render = true;
+
+        match event {
+            InputEvent::Keyboard { event } => self.on_keyboard_event(event),
+            InputEvent::PointerMotion { event } => self.on_pointer_motion(event),
+            InputEvent::PointerMotionAbsolute { event } => self.on_pointer_motion_absolute(event),
+            InputEvent::PointerButton { event } => self.on_pointer_button(event),
+            InputEvent::PointerAxis { event } => self.on_pointer_axis(event),
+            InputEvent::TouchDown { event } => self.on_touch_down(event),
+            InputEvent::TouchMotion { event } => self.on_touch_motion(event),
+            InputEvent::TouchUp { event } => self.on_touch_up(event),
+            InputEvent::TouchCancel { event } => self.on_touch_cancel(event),
+            InputEvent::TouchFrame { event } => self.on_touch_frame(event),
+            _ => {}
+        }
+    }
+
+    /// Current output size in logical pixels, used to transform absolute
+    /// pointer and touch coordinates.
+    fn output_size(&self) -> Size<i32, Logical> {
+        self.smithay_state
+            .output
+            .current_mode()
+            .map(|mode| mode.size)
+            .unwrap_or_default()
+    }
+
+    fn clamp_cursor_position(&self, position: Point<f64, Logical>) -> Point<f64, Logical> {
+        let size = self.output_size();
+
+        Point::from((
+            position.x.clamp(0.0, size.w as f64),
+            position.y.clamp(0.0, size.h as f64),
+        ))
+    }
+
+    /// Surface (if any) under `position`, together with its absolute
+    /// position, for use as pointer/touch focus.
+    fn surface_under(&self, position: Point<f64, Logical>) -> Option<(WlSurface, Point<i32, Logical>)> {
+        let (window, location) = self.smithay_state.space.element_under(position)?;
+        let (surface, surface_offset) =
+            window.surface_under(position - location.to_f64(), WindowSurfaceType::ALL)?;
+
+        Some((surface, location + surface_offset))
+    }
+
+    fn on_pointer_motion(&mut self, event: PointerMotionEvent) {
+        let (dx, dy) = (event.delta_x(), event.delta_y());
+
+        self.cursor_position =
+            self.clamp_cursor_position(self.cursor_position + Point::from((dx, dy)));
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let under = self.surface_under(self.cursor_position);
+        let pointer = self.smithay_state.seat.get_pointer().unwrap();
+
+        pointer.motion(
+            self,
+            under,
+            &MotionEvent {
+                location: self.cursor_position,
+                serial,
+                time: event.time_msec(),
+            },
+        );
+        pointer.frame(self);
+
+        self.app.world_mut().send_event(CursorMoved {
+            window: self.primary_window,
+            position: Vec2::new(self.cursor_position.x as f32, self.cursor_position.y as f32),
+            delta: Some(Vec2::new(dx as f32, dy as f32)),
+        });
+    }
+
+    fn on_pointer_motion_absolute(&mut self, event: PointerMotionAbsoluteEvent) {
+        self.cursor_position = event.position_transformed(self.output_size());
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let under = self.surface_under(self.cursor_position);
+        let pointer = self.smithay_state.seat.get_pointer().unwrap();
+
+        pointer.motion(
+            self,
+            under,
+            &MotionEvent {
+                location: self.cursor_position,
+                serial,
+                time: event.time_msec(),
+            },
+        );
+        pointer.frame(self);
+
+        self.app.world_mut().send_event(CursorMoved {
+            window: self.primary_window,
+            position: Vec2::new(self.cursor_position.x as f32, self.cursor_position.y as f32),
+            delta: None,
+        });
+    }
+
+    fn on_pointer_button(&mut self, event: PointerButtonEvent) {
+        let serial = SERIAL_COUNTER.next_serial();
+        let button_state = event.state();
+        let pointer = self.smithay_state.seat.get_pointer().unwrap();
+
+        pointer.button(
+            self,
+            &ButtonEvent {
+                button: event.button_code(),
+                state: button_state,
+                serial,
+                time: event.time_msec(),
+            },
+        );
+        pointer.frame(self);
+
+        let Some(button) = crate::convert::button_to_bevy(event.button_code()) else {
+            return;
+        };
+
+        let state = match button_state {
+            ButtonState::Pressed => BevyButtonState::Pressed,
+            ButtonState::Released => BevyButtonState::Released,
+        };
+
+        self.app.world_mut().send_event(MouseButtonInput {
+            button,
+            state,
+            window: self.primary_window,
+        });
+    }
+
+    fn on_pointer_axis(&mut self, event: LibinputPointerAxisEvent) {
+        let source: AxisSource = event.source();
+        let horizontal = event.amount(Axis::Horizontal).unwrap_or(0.0);
+        let vertical = event.amount(Axis::Vertical).unwrap_or(0.0);
+
+        let mut frame = AxisFrame::new(event.time_msec()).source(source);
+
+        if horizontal != 0.0 {
+            frame = frame.value(Axis::Horizontal, horizontal);
+        }
+
+        if vertical != 0.0 {
+            frame = frame.value(Axis::Vertical, vertical);
+        }
+
+        let pointer = self.smithay_state.seat.get_pointer().unwrap();
+        pointer.axis(self, frame);
+        pointer.frame(self);
+
+        self.app.world_mut().send_event(MouseWheel {
+            unit: crate::convert::axis_source_to_bevy(source),
+            x: horizontal as f32,
+            y: vertical as f32,
+            window: self.primary_window,
+        });
+    }
+
+    fn on_touch_down(&mut self, event: TouchDownEvent) {
+        let Some(touch) = self.smithay_state.seat.get_touch() else {
+            return;
+        };
+
+        let position = event.position_transformed(self.output_size());
+        let serial = SERIAL_COUNTER.next_serial();
+        let under = self.surface_under(position);
+
+        self.touch_positions.insert(touch_slot_id(event.slot()), position);
+
+        touch.down(
+            self,
+            under,
+            &smithay::input::touch::DownEvent {
+                slot: event.slot(),
+                location: position,
+                serial,
+                time: event.time_msec(),
+            },
+        );
+
+        self.app.world_mut().send_event(TouchInput {
+            phase: BevyTouchPhase::Started,
+            position: Vec2::new(position.x as f32, position.y as f32),
+            force: None,
+            id: touch_slot_id(event.slot()),
+            window: self.primary_window,
+        });
+    }
+
+    fn on_touch_motion(&mut self, event: TouchMotionEvent) {
+        let Some(touch) = self.smithay_state.seat.get_touch() else {
+            return;
+        };
+
+        let position = event.position_transformed(self.output_size());
+        let under = self.surface_under(position);
+
+        self.touch_positions.insert(touch_slot_id(event.slot()), position);
+
+        touch.motion(
+            self,
+            under,
+            &smithay::input::touch::MotionEvent {
+                slot: event.slot(),
+                location: position,
+                time: event.time_msec(),
+            },
+        );
+
+        self.app.world_mut().send_event(TouchInput {
+            phase: BevyTouchPhase::Moved,
+            position: Vec2::new(position.x as f32, position.y as f32),
+            force: None,
+            id: touch_slot_id(event.slot()),
+            window: self.primary_window,
+        });
+    }
+
+    fn on_touch_up(&mut self, event: TouchUpEvent) {
+        let Some(touch) = self.smithay_state.seat.get_touch() else {
+            return;
+        };
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let slot = touch_slot_id(event.slot());
+        let position = self.touch_positions.remove(&slot).unwrap_or(self.cursor_position);
+
+        touch.up(
+            self,
+            &smithay::input::touch::UpEvent {
+                slot: event.slot(),
+                serial,
+                time: event.time_msec(),
+            },
+        );
+
+        self.app.world_mut().send_event(TouchInput {
+            phase: BevyTouchPhase::Ended,
+            position: Vec2::new(position.x as f32, position.y as f32),
+            force: None,
+            id: slot,
+            window: self.primary_window,
+        });
+    }
+
+    fn on_touch_cancel(&mut self, event: TouchCancelEvent) {
+        let Some(touch) = self.smithay_state.seat.get_touch() else {
+            return;
+        };
+
+        touch.cancel(self);
+
+        let slot = touch_slot_id(event.slot());
+        let position = self.touch_positions.remove(&slot).unwrap_or(self.cursor_position);
+
+        self.app.world_mut().send_event(TouchInput {
+            phase: BevyTouchPhase::Canceled,
+            position: Vec2::new(position.x as f32, position.y as f32),
+            force: None,
+            id: slot,
+            window: self.primary_window,
+        });
+    }
+
+    fn on_touch_frame(&mut self, _event: TouchFrameEvent) {
+        if let Some(touch) = self.smithay_state.seat.get_touch() {
+            touch.frame(self);
         }
     }
 
@@ -456,11 +1003,18 @@ impl SmithayAppRunnerState {
         let time = event.time_msec();
 
         keyboard
-            .input(self, keycode, state, serial, time, Self::on_input)
+            .input(self, keycode, state, serial, time, |data, modifiers, keysym| {
+                Self::on_input(data, modifiers, keysym, state)
+            })
             .unwrap_or(());
     }
 
-    fn on_input(&mut self, modifiers: &ModifiersState, keysym: KeysymHandle) -> FilterResult<()> {
+    fn on_input(
+        &mut self,
+        modifiers: &ModifiersState,
+        keysym: KeysymHandle,
+        key_state: KeyState,
+    ) -> FilterResult<()> {
         if let Some(character) = keysym.modified_sym().key_char() {
             use bevy::input::keyboard::Key;
             use std::iter;
@@ -475,6 +1029,30 @@ impl SmithayAppRunnerState {
 
         println!("{keysym:?} -> {keycode:?}");
 
+        if modifiers.alt && key_state == KeyState::Pressed {
+            use bevy::input::keyboard::KeyCode;
+
+            let mut relayout = true;
+
+            match keycode {
+                KeyCode::KeyH => self.smithay_state.layout.focus_left(),
+                KeyCode::KeyL => self.smithay_state.layout.focus_right(),
+                KeyCode::KeyJ if modifiers.shift => self.smithay_state.layout.move_column(-1),
+                KeyCode::KeyK if modifiers.shift => self.smithay_state.layout.move_column(1),
+                KeyCode::Equal => self.smithay_state.layout.grow_focused(COLUMN_RESIZE_STEP),
+                KeyCode::Minus => self.smithay_state.layout.shrink_focused(COLUMN_RESIZE_STEP),
+                _ => relayout = false,
+            }
+
+            if relayout {
+                self.smithay_state
+                    .layout
+                    .relayout(&mut self.smithay_state.space, &self.smithay_state.output);
+
+                return FilterResult::Intercept(());
+            }
+        }
+
         let world = self.app.world_mut();
 
         if keycode == bevy::input::keyboard::KeyCode::Escape {
@@ -488,37 +1066,77 @@ impl SmithayAppRunnerState {
         const FRAME_RATE: Duration = Duration::from_secs(1).checked_div(144).unwrap();
 
         let mut start = Instant::now();
+        let mut idle = false;
 
         loop {
-            let _result = event_loop.dispatch(FRAME_RATE, self);
+            // `idle` alone can go stale: an event can set `pending_render`
+            // in a dispatch that lands inside the current frame period,
+            // skipping the render block below entirely, and nothing else
+            // would ever wake a `None` (block-forever) dispatch to pick it
+            // back up. Never block indefinitely while a render is pending.
+            let timeout = if idle && !self.pending_render { None } else { Some(FRAME_RATE) };
+            let _result = event_loop.dispatch(timeout, self);
 
             let now = Instant::now();
-            if now.duration_since(start) > FRAME_RATE {
+            if !self.session_paused && now.duration_since(start) > FRAME_RATE {
                 start = now;
 
-                let render_device = self.app.world_mut().resource::<RenderDevice>();
-                let (dmabuf, _slot) = self.gbm_surface.next_buffer().unwrap();
-
-                let gbm_buffer = dmabuf
-                    .import_to(&self.gbm_device, GbmBufferFlags::empty())
-                    .unwrap();
-
-                let handle = ManualTextureViewHandle(0);
-                let (_texture, manual_texture_view) =
-                    util::import_texture(render_device, &gbm_buffer).unwrap();
-
-                self.app
-                    .world_mut()
-                    .resource_mut::<ManualTextureViews>()
-                    .insert(handle, manual_texture_view);
-
-                let target = RenderTarget::TextureView(handle);
-
-                self.app.insert_resource(MainTexture(target));
+                let bevy_ready = self.app.plugins_state() == PluginsState::Cleaned;
+                let bevy_damaged = bevy_ready && std::mem::take(&mut self.pending_render);
+
+                // Every connected surface tracks and is fed its own damage
+                // independently, so a second monitor/GPU actually keeps
+                // receiving fresh pixels instead of only the first one found.
+                let mut any_damage = false;
+
+                for device in self.devices.values_mut() {
+                    for surface in device.surfaces.values_mut() {
+                        let damage = surface.damage_tracker.damage(
+                            &self.smithay_state.space,
+                            &surface.output,
+                            bevy_damaged,
+                        );
+
+                        if damage.is_empty() {
+                            continue;
+                        }
+
+                        any_damage = true;
+
+                        let Ok((dmabuf, _slot)) = surface.gbm_surface.next_buffer() else {
+                            continue;
+                        };
+
+                        let Ok(gbm_buffer) =
+                            dmabuf.import_to(&device.gbm_device, GbmBufferFlags::empty())
+                        else {
+                            continue;
+                        };
+
+                        let render_device = self.app.world_mut().resource::<RenderDevice>();
+
+                        if let Ok((_texture, manual_texture_view)) =
+                            util::import_texture(render_device, &gbm_buffer)
+                        {
+                            self.app
+                                .world_mut()
+                                .resource_mut::<ManualTextureViews>()
+                                .insert(surface.texture_handle, manual_texture_view);
+
+                            if surface.texture_handle == ManualTextureViewHandle(0) {
+                                self.app.insert_resource(MainTexture(RenderTarget::TextureView(
+                                    surface.texture_handle,
+                                )));
+                            }
+                        }
+
+                        let _ = surface.gbm_surface.queue_buffer(None, Some(damage), ());
+                    }
+                }
 
-                self.gbm_surface.queue_buffer(None, None, ()).unwrap();
+                idle = !any_damage;
 
-                if self.app.plugins_state() == PluginsState::Cleaned {
+                if bevy_ready {
                     self.app.update()
                 }
 
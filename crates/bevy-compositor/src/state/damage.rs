@@ -0,0 +1,75 @@
+use smithay::desktop::{Space, Window};
+use smithay::output::Output;
+use smithay::utils::{Physical, Rectangle};
+
+/// Per-output damage accumulator. Bevy re-renders its whole render target
+/// every tick rather than tracking sub-frame damage itself, so a "Bevy
+/// damaged" tick contributes the full output; on top of that this tracks
+/// `space` element geometry so a window move/map/unmap without a fresh Bevy
+/// frame still produces the right damage.
+pub struct DamageTracker {
+    previous_geometry: Vec<(Window, Rectangle<i32, Physical>)>,
+    force_full: bool,
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self {
+            previous_geometry: Vec::new(),
+            force_full: true,
+        }
+    }
+
+    /// Forces the next call to [`Self::damage`] to report the whole output
+    /// as damaged. Used after a mode change or session resume, where the
+    /// scanout buffer contents are no longer known to match what's tracked.
+    pub fn force_full_damage(&mut self) {
+        self.force_full = true;
+    }
+
+    /// Damage accumulated since the last call: the whole output if a full
+    /// redraw was forced or `bevy_damaged` is set, otherwise just the window
+    /// geometry that moved, appeared, or disappeared since last time.
+    pub fn damage(
+        &mut self,
+        space: &Space<Window>,
+        output: &Output,
+        bevy_damaged: bool,
+    ) -> Vec<Rectangle<i32, Physical>> {
+        let current_geometry: Vec<_> = space
+            .elements()
+            .filter_map(|window| Some((window.clone(), space.element_geometry(window)?)))
+            .map(|(window, geometry)| (window, geometry.to_physical(1)))
+            .collect();
+
+        if self.force_full || bevy_damaged {
+            self.force_full = false;
+            self.previous_geometry = current_geometry;
+
+            let output_geometry = space
+                .output_geometry(output)
+                .unwrap_or_default()
+                .to_physical(1);
+
+            return vec![output_geometry];
+        }
+
+        // Compare (window, geometry) pairs, not bare geometry: a different
+        // window ending up at the same on-screen rectangle (e.g. one closing
+        // while another takes its tile) must still be treated as damage.
+        let damage = current_geometry
+            .iter()
+            .filter(|entry| !self.previous_geometry.contains(entry))
+            .chain(
+                self.previous_geometry
+                    .iter()
+                    .filter(|entry| !current_geometry.contains(entry)),
+            )
+            .map(|(_, geometry)| *geometry)
+            .collect();
+
+        self.previous_geometry = current_geometry;
+
+        damage
+    }
+}
@@ -0,0 +1,41 @@
+use super::damage::DamageTracker;
+use bevy::render::camera::ManualTextureViewHandle;
+use smithay::backend::allocator::gbm::{GbmAllocator, GbmDevice};
+use smithay::backend::drm::{DrmDevice, DrmDeviceFd, GbmBufferedSurface, PlaneClaim};
+use smithay::output::Output;
+use smithay::reexports::calloop::RegistrationToken;
+use smithay::reexports::drm::control::{connector, crtc};
+use smithay::reexports::wayland_server::backend::GlobalId;
+use smithay_drm_extras::drm_scanner::{DrmScanner, SimpleCrtcMapper};
+use std::collections::HashMap;
+
+/// Per-CRTC scanout state for one connected output on a [`Device`].
+pub struct Surface {
+    pub connector: connector::Handle,
+    pub output: Output,
+    /// `output`'s `wl_output` global, kept around so it can be withdrawn via
+    /// `remove_global` when this surface is torn down.
+    pub global: GlobalId,
+    /// Stable handle this surface's scanout buffer is imported into Bevy's
+    /// `ManualTextureViews` under, so every connected surface keeps its own
+    /// texture instead of fighting over a single shared one.
+    pub texture_handle: ManualTextureViewHandle,
+    /// Tracks this surface's own damage, independent of every other
+    /// connected output.
+    pub damage_tracker: DamageTracker,
+    pub plane_claim: PlaneClaim,
+    pub gbm_surface: GbmBufferedSurface<GbmAllocator<DrmDeviceFd>, ()>,
+}
+
+/// Everything mocha tracks for a single GPU, keyed by its [`DrmNode`] in
+/// `SmithayAppRunnerState::devices`.
+pub struct Device {
+    pub drm_device: DrmDevice,
+    pub gbm_device: GbmDevice<DrmDeviceFd>,
+    pub drm_scanner: DrmScanner<SimpleCrtcMapper>,
+    pub surfaces: HashMap<crtc::Handle, Surface>,
+    /// Event loop registration for this device's DRM event source, removed
+    /// when the device itself is removed so a dropped device doesn't leave
+    /// a dangling source registered forever.
+    pub notifier_token: RegistrationToken,
+}
@@ -0,0 +1,216 @@
+use smithay::desktop::{Space, Window};
+use smithay::output::Output;
+use smithay::utils::{Logical, Point};
+use smithay::wayland::shell::xdg::ToplevelSurface;
+
+/// Horizontal gap, in logical pixels, inserted between adjacent columns.
+const COLUMN_GAP: i32 = 16;
+
+/// Default width given to a column when it is first created.
+const DEFAULT_COLUMN_WIDTH: i32 = 1280;
+
+/// Minimum width a column may be shrunk to.
+const MIN_COLUMN_WIDTH: i32 = 320;
+
+/// A single vertical stack of windows occupying the full output height.
+pub struct Column {
+    pub windows: Vec<Window>,
+    pub width: i32,
+}
+
+impl Column {
+    fn new(window: Window) -> Self {
+        Self {
+            windows: vec![window],
+            width: DEFAULT_COLUMN_WIDTH,
+        }
+    }
+
+    fn contains(&self, window: &Window) -> bool {
+        self.windows.iter().any(|w| w == window)
+    }
+
+    /// Heights for each window in the column, splitting `output_height` evenly.
+    fn window_heights(&self, output_height: i32) -> Vec<i32> {
+        let count = self.windows.len() as i32;
+        let base = output_height / count;
+        let remainder = output_height - base * count;
+
+        (0..count)
+            .map(|index| if index < remainder { base + 1 } else { base })
+            .collect()
+    }
+}
+
+/// A PaperWM-style scrollable-tiling workspace: an infinite horizontal strip
+/// of [`Column`]s, scrolled into view through `view_offset`.
+pub struct Layout {
+    pub columns: Vec<Column>,
+    pub focused_column: usize,
+    pub view_offset: i32,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            focused_column: 0,
+            view_offset: 0,
+        }
+    }
+}
+
+impl Layout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a new column containing `window` to the right of focus and
+    /// focuses it.
+    pub fn insert_window(&mut self, window: Window) {
+        let index = if self.columns.is_empty() {
+            0
+        } else {
+            self.focused_column + 1
+        };
+
+        self.columns.insert(index, Column::new(window));
+        self.focused_column = index;
+    }
+
+    /// Removes `window`'s column, re-packing the remaining columns.
+    pub fn remove_window(&mut self, window: &Window) {
+        let Some(index) = self.columns.iter().position(|column| column.contains(window)) else {
+            return;
+        };
+
+        self.columns.remove(index);
+
+        if index < self.focused_column {
+            self.focused_column -= 1;
+        } else if self.focused_column >= self.columns.len() && !self.columns.is_empty() {
+            self.focused_column = self.columns.len() - 1;
+        }
+    }
+
+    /// Removes the column containing `surface`'s window and returns that
+    /// window, so the caller can unmap it from `space`. Looks up `surface`
+    /// against `columns` rather than `space.elements()`: `relayout` unmaps
+    /// columns scrolled off either edge of the output, so a closed window
+    /// several columns from focus would otherwise never be found.
+    pub fn remove_toplevel(&mut self, surface: &ToplevelSurface) -> Option<Window> {
+        let window = self
+            .columns
+            .iter()
+            .flat_map(|column| &column.windows)
+            .find(|window| window.toplevel() == Some(surface))
+            .cloned()?;
+
+        self.remove_window(&window);
+
+        Some(window)
+    }
+
+    pub fn focus_left(&mut self) {
+        if self.focused_column > 0 {
+            self.focused_column -= 1;
+        }
+    }
+
+    pub fn focus_right(&mut self) {
+        if self.focused_column + 1 < self.columns.len() {
+            self.focused_column += 1;
+        }
+    }
+
+    /// Swaps the focused column with its left or right neighbour.
+    pub fn move_column(&mut self, delta: i32) {
+        let Some(target) = self.focused_column.checked_add_signed(delta as isize) else {
+            return;
+        };
+
+        if target >= self.columns.len() {
+            return;
+        }
+
+        self.columns.swap(self.focused_column, target);
+        self.focused_column = target;
+    }
+
+    pub fn grow_focused(&mut self, delta: i32) {
+        if let Some(column) = self.columns.get_mut(self.focused_column) {
+            column.width += delta;
+        }
+    }
+
+    pub fn shrink_focused(&mut self, delta: i32) {
+        if let Some(column) = self.columns.get_mut(self.focused_column) {
+            column.width = (column.width - delta).max(MIN_COLUMN_WIDTH);
+        }
+    }
+
+    /// X offset of the left edge of `index`'s column, ignoring `view_offset`.
+    fn column_x(&self, index: usize) -> i32 {
+        self.columns[..index]
+            .iter()
+            .map(|column| column.width + COLUMN_GAP)
+            .sum()
+    }
+
+    /// Recomputes `view_offset` so the focused column is fully visible,
+    /// scrolling the minimum distance necessary.
+    fn focus_view(&mut self, output_width: i32) {
+        let Some(column) = self.columns.get(self.focused_column) else {
+            return;
+        };
+
+        let left = self.column_x(self.focused_column);
+        let right = left + column.width;
+
+        if right - self.view_offset > output_width {
+            self.view_offset = right - output_width;
+        }
+
+        if left - self.view_offset < 0 {
+            self.view_offset = left;
+        }
+    }
+
+    /// Recomputes `view_offset` for the current focus and maps every window
+    /// to its on-screen position, unmapping columns scrolled fully off
+    /// either edge of the output.
+    pub fn relayout(&mut self, space: &mut Space<Window>, output: &Output) {
+        let output_size = output
+            .current_mode()
+            .map(|mode| mode.size)
+            .unwrap_or_default();
+
+        self.focus_view(output_size.w);
+
+        for (index, column) in self.columns.iter().enumerate() {
+            let x = self.column_x(index) - self.view_offset;
+            let heights = column.window_heights(output_size.h);
+            let mut y = 0;
+
+            for (window, height) in column.windows.iter().zip(heights) {
+                if x + column.width <= 0 || x >= output_size.w {
+                    space.unmap_elem(window);
+                } else {
+                    let location: Point<i32, Logical> = (x, y).into();
+
+                    window.toplevel().map(|toplevel| {
+                        toplevel.with_pending_state(|state| {
+                            state.size = Some((column.width, height).into());
+                        });
+
+                        toplevel.send_pending_configure();
+                    });
+
+                    space.map_element(window.clone(), location, false);
+                }
+
+                y += height;
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use super::SmithayAppRunnerState;
+use smithay::desktop::{PopupKind, Window};
+use smithay::reexports::wayland_server::protocol::wl_seat::WlSeat;
+use smithay::utils::Serial;
+use smithay::wayland::shell::xdg::{
+    PopupSurface, PositionerState, ToplevelSurface, XdgShellHandler, XdgShellState,
+};
+
+impl XdgShellHandler for SmithayAppRunnerState {
+    fn xdg_shell_state(&mut self) -> &mut XdgShellState {
+        &mut self.smithay_state.xdg_shell_state
+    }
+
+    fn new_toplevel(&mut self, surface: ToplevelSurface) {
+        surface.with_pending_state(|state| {
+            state.states.set(smithay::wayland::shell::xdg::ToplevelState::Activated);
+        });
+
+        surface.send_configure();
+
+        let window = Window::new_wayland_window(surface);
+
+        self.smithay_state.layout.insert_window(window);
+        self.smithay_state
+            .layout
+            .relayout(&mut self.smithay_state.space, &self.smithay_state.output);
+    }
+
+    fn new_popup(&mut self, surface: PopupSurface, _positioner: PositionerState) {
+        let _ = self
+            .smithay_state
+            .popup_manager
+            .track_popup(PopupKind::Xdg(surface));
+    }
+
+    fn grab(&mut self, surface: PopupSurface, _seat: WlSeat, _serial: Serial) {
+        let _ = self
+            .smithay_state
+            .popup_manager
+            .grab_popup(surface.wl_surface().clone(), PopupKind::Xdg(surface), 0.into());
+    }
+
+    fn reposition_request(
+        &mut self,
+        surface: PopupSurface,
+        positioner: PositionerState,
+        token: u32,
+    ) {
+        surface.with_pending_state(|state| {
+            let geometry = positioner.get_geometry();
+            state.geometry = geometry;
+            state.positioner = positioner;
+        });
+
+        surface.send_repositioned(token);
+    }
+
+    fn toplevel_destroyed(&mut self, surface: ToplevelSurface) {
+        if let Some(window) = self.smithay_state.layout.remove_toplevel(&surface) {
+            self.smithay_state.space.unmap_elem(&window);
+            self.smithay_state
+                .layout
+                .relayout(&mut self.smithay_state.space, &self.smithay_state.output);
+        }
+    }
+}
+
+smithay::delegate_xdg_shell!(SmithayAppRunnerState);